@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::command;
+
+use crate::{file_meta_from_entry, FileMeta};
+
+/// Bytes read from the head of a file when computing the cheap partial hash
+/// used to split size-equal buckets before paying for a full streaming hash.
+const PARTIAL_HASH_BYTES: usize = 4 * 1024;
+/// Chunk size used while streaming a file through the full hash so we never
+/// hold more than one chunk of it in memory at a time.
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Serialize)]
+pub(crate) struct DuplicateGroup {
+    files: Vec<FileMeta>,
+    reclaimable_bytes: u64,
+}
+
+struct Candidate {
+    path: PathBuf,
+    size: u64,
+}
+
+/// Walks `path` and groups files that share identical content.
+///
+/// Candidates are first bucketed by size (a necessary condition for
+/// equality), then size-equal buckets are split further by a partial hash of
+/// the first [`PARTIAL_HASH_BYTES`], and only files that still collide pay
+/// for a full streaming hash. Zero-length files and symlinks are skipped so
+/// they can't produce false-positive groups or walk into a cycle.
+#[command]
+pub(crate) fn find_duplicates(path: String) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<Candidate>> = HashMap::new();
+    collect_candidates(&PathBuf::from(path), &mut by_size);
+
+    let mut groups = Vec::new();
+    for (_size, candidates) in by_size {
+        if candidates.len() < 2 {
+            continue;
+        }
+        for (_partial, bucket) in partial_hash_buckets(candidates) {
+            if bucket.len() < 2 {
+                continue;
+            }
+            groups.extend(full_hash_groups(bucket));
+        }
+    }
+    groups
+}
+
+/// Bucketing pass: only `symlink_metadata` is read here, never a file's
+/// contents, so a tree of files with all-unique sizes never gets opened.
+/// Content is only read later, for files that actually collide on size.
+fn collect_candidates(dir: &Path, by_size: &mut HashMap<u64, Vec<Candidate>>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_symlink() {
+            continue;
+        }
+        if metadata.is_dir() {
+            collect_candidates(&path, by_size);
+        } else if metadata.len() > 0 {
+            let size = metadata.len();
+            by_size.entry(size).or_default().push(Candidate { path, size });
+        }
+    }
+}
+
+fn partial_hash_buckets(candidates: Vec<Candidate>) -> HashMap<[u8; 32], Vec<Candidate>> {
+    let mut buckets: HashMap<[u8; 32], Vec<Candidate>> = HashMap::new();
+    for candidate in candidates {
+        match partial_hash(&candidate.path) {
+            Ok(hash) => buckets.entry(hash).or_default().push(candidate),
+            Err(_) => continue,
+        }
+    }
+    buckets
+}
+
+fn partial_hash(path: &Path) -> io::Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0u8; PARTIAL_HASH_BYTES];
+    let mut hasher = Sha256::new();
+    let mut read = 0;
+    while read < buf.len() {
+        let n = file.read(&mut buf[read..])?;
+        if n == 0 {
+            break;
+        }
+        read += n;
+    }
+    hasher.update(&buf[..read]);
+    Ok(hasher.finalize().into())
+}
+
+fn full_hash_groups(candidates: Vec<Candidate>) -> Vec<DuplicateGroup> {
+    let mut by_hash: HashMap<[u8; 32], Vec<Candidate>> = HashMap::new();
+    for candidate in candidates {
+        match full_hash(&candidate.path) {
+            Ok(hash) => by_hash.entry(hash).or_default().push(candidate),
+            Err(_) => continue,
+        }
+    }
+    by_hash
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            let size = group[0].size;
+            let reclaimable_bytes = size * (group.len() as u64 - 1);
+            // Content sniffing only happens here, for files confirmed to be
+            // in a duplicate group, not for every size-bucket candidate.
+            let files = group
+                .iter()
+                .filter_map(|c| file_meta_from_entry(&c.path))
+                .collect();
+            DuplicateGroup {
+                files,
+                reclaimable_bytes,
+            }
+        })
+        .collect()
+}
+
+/// Streaming SHA-256 of a whole file, hex-encoded. Shared with the index
+/// subsystem so a changed file only needs to be hashed once per rescan.
+pub(crate) fn full_hash_hex(path: &Path) -> io::Result<String> {
+    full_hash(path).map(hex::encode)
+}
+
+fn full_hash(path: &Path) -> io::Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; STREAM_CHUNK_BYTES];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().into())
+}
@@ -0,0 +1,285 @@
+use std::fs;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::time::UNIX_EPOCH;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+
+use crate::{file_meta_from_entry, FileMeta};
+
+const DB_FILE_NAME: &str = "index.sqlite";
+
+/// One row of the persistent index: enough to detect whether a path changed
+/// on the next rescan without re-hashing it.
+struct IndexedFile {
+    size: u64,
+    modified_secs: i64,
+}
+
+/// A row of the index read back as-is, with no filesystem access — unlike
+/// `FileMeta`, it carries only what's actually stored, so it stays accurate
+/// even if the file has since moved or been deleted on disk.
+#[derive(Serialize)]
+pub(crate) struct IndexedEntry {
+    name: String,
+    path: String,
+    file_type: String,
+    category: String,
+    size: u64,
+    created: u64,
+    modified: u64,
+}
+
+#[derive(Serialize, Default)]
+pub(crate) struct RescanDelta {
+    added: Vec<FileMeta>,
+    removed: Vec<String>,
+    modified: Vec<FileMeta>,
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct IndexFilter {
+    name_contains: Option<String>,
+    file_type: Option<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+}
+
+/// Creates the SQLite database in the Tauri app data directory and ensures
+/// the `files` table exists. Safe to call on every app start.
+#[command]
+pub(crate) fn init_index(app: AppHandle) -> Result<(), String> {
+    let conn = open_db(&app).map_err(|e| e.to_string())?;
+    create_schema(&conn).map_err(|e| e.to_string())
+}
+
+/// Walks `path`, diffs it against the stored index, and persists the result.
+///
+/// Entries whose stored `size`/`modified` still match the live metadata are
+/// assumed unchanged and are not re-hashed; everything else is re-hashed and
+/// reported back as an added or modified delta, and paths that disappeared
+/// from disk are reported as removed.
+#[command]
+pub(crate) fn rescan(app: AppHandle, path: String) -> Result<RescanDelta, String> {
+    let mut conn = open_db(&app).map_err(|e| e.to_string())?;
+    create_schema(&conn).map_err(|e| e.to_string())?;
+
+    let root = PathBuf::from(&path);
+    let mut seen = Vec::new();
+    walk(&root, &mut seen);
+
+    let mut delta = RescanDelta::default();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let mut seen_paths = Vec::with_capacity(seen.len());
+    for (file_path, metadata) in &seen {
+        let path_str = file_path.to_string_lossy().to_string();
+        seen_paths.push(path_str.clone());
+        let modified_secs = modified_secs(metadata);
+        let existing = fetch(&tx, &path_str).map_err(|e| e.to_string())?;
+
+        if let Some(row) = &existing {
+            if row.size == metadata.len() && row.modified_secs == modified_secs {
+                continue;
+            }
+        }
+
+        let hash = crate::duplicates::full_hash_hex(file_path).map_err(|e| e.to_string())?;
+        let Some(meta) = file_meta_from_entry(file_path) else {
+            continue;
+        };
+        upsert(&tx, &path_str, &meta, &hash).map_err(|e| e.to_string())?;
+        if existing.is_some() {
+            delta.modified.push(meta);
+        } else {
+            delta.added.push(meta);
+        }
+    }
+
+    for stale_path in stale_paths(&tx, &root, &seen_paths).map_err(|e| e.to_string())? {
+        remove(&tx, &stale_path).map_err(|e| e.to_string())?;
+        delta.removed.push(stale_path);
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(delta)
+}
+
+/// Reads previously scanned files out of the index without touching disk,
+/// optionally narrowed by `filter`. Every field comes straight from the
+/// stored row, so a file that has since moved or vanished on disk still
+/// returns its last-known state instead of being silently dropped.
+#[command]
+pub(crate) fn query_index(app: AppHandle, filter: IndexFilter) -> Result<Vec<IndexedEntry>, String> {
+    let conn = open_db(&app).map_err(|e| e.to_string())?;
+    create_schema(&conn).map_err(|e| e.to_string())?;
+
+    let mut sql = String::from(
+        "SELECT name, path, file_type, category, size, created_secs, modified_secs FROM files WHERE 1=1",
+    );
+    let mut bindings: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(name) = &filter.name_contains {
+        sql.push_str(&format!(" AND name LIKE ? ESCAPE '{LIKE_ESCAPE}'"));
+        bindings.push(Box::new(escape_like(name)));
+    }
+    if let Some(file_type) = &filter.file_type {
+        sql.push_str(" AND file_type = ? COLLATE NOCASE");
+        bindings.push(Box::new(file_type.clone()));
+    }
+    if let Some(min_size) = filter.min_size {
+        sql.push_str(" AND size >= ?");
+        bindings.push(Box::new(min_size));
+    }
+    if let Some(max_size) = filter.max_size {
+        sql.push_str(" AND size <= ?");
+        bindings.push(Box::new(max_size));
+    }
+
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let params: Vec<&dyn rusqlite::ToSql> = bindings.iter().map(|b| b.as_ref()).collect();
+    stmt.query_map(params.as_slice(), |row| {
+        Ok(IndexedEntry {
+            name: row.get(0)?,
+            path: row.get(1)?,
+            file_type: row.get(2)?,
+            category: row.get(3)?,
+            size: row.get::<_, i64>(4)? as u64,
+            created: row.get::<_, i64>(5)? as u64,
+            modified: row.get::<_, i64>(6)? as u64,
+        })
+    })
+    .map_err(|e| e.to_string())?
+    .collect::<rusqlite::Result<Vec<_>>>()
+    .map_err(|e| e.to_string())
+}
+
+/// `LIKE` escape character used throughout this module. Deliberately not
+/// `\`: on Windows that's also `std::path::MAIN_SEPARATOR`, so a prefix like
+/// `C:\foo\` escaped with `\` would turn the trailing separator into an
+/// escape for a literal `%` instead of leaving the wildcard active, and
+/// `stale_paths` would never match anything.
+const LIKE_ESCAPE: char = '^';
+
+/// Escapes `%`, `_`, and the escape character itself so a user-supplied
+/// substring can't smuggle in its own SQL `LIKE` wildcards.
+fn escape_like(raw: &str) -> String {
+    format!("%{}%", escape_like_literal(raw))
+}
+
+fn open_db(app: &AppHandle) -> rusqlite::Result<Connection> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let _ = fs::create_dir_all(&dir);
+    Connection::open(dir.join(DB_FILE_NAME))
+}
+
+fn create_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS files (
+            path TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            file_type TEXT NOT NULL,
+            category TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            created_secs INTEGER NOT NULL,
+            modified_secs INTEGER NOT NULL,
+            hash TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn fetch(conn: &Connection, path: &str) -> rusqlite::Result<Option<IndexedFile>> {
+    conn.query_row(
+        "SELECT size, modified_secs FROM files WHERE path = ?1",
+        params![path],
+        |row| {
+            Ok(IndexedFile {
+                size: row.get(0)?,
+                modified_secs: row.get(1)?,
+            })
+        },
+    )
+    .optional()
+}
+
+fn upsert(conn: &Connection, path: &str, meta: &FileMeta, hash: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO files (path, name, file_type, category, size, created_secs, modified_secs, hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT(path) DO UPDATE SET
+            name = ?2, file_type = ?3, category = ?4, size = ?5,
+            created_secs = ?6, modified_secs = ?7, hash = ?8",
+        params![
+            path,
+            meta.name,
+            meta.file_type,
+            meta.category,
+            meta.size as i64,
+            meta.created as i64,
+            meta.modified as i64,
+            hash,
+        ],
+    )?;
+    Ok(())
+}
+
+fn remove(conn: &Connection, path: &str) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM files WHERE path = ?1", params![path])?;
+    Ok(())
+}
+
+/// Finds indexed rows under `root` that weren't encountered by the current
+/// walk. Matches on a separator-anchored prefix (with `%`/`_` escaped) so a
+/// sibling directory that merely shares a name prefix, e.g. `docs2` next to
+/// `docs`, isn't mistaken for a descendant and wrongly marked removed.
+fn stale_paths(conn: &Connection, root: &Path, seen_paths: &[String]) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT path FROM files WHERE path = ?1 OR path LIKE ?2 ESCAPE '{LIKE_ESCAPE}'"
+    ))?;
+    let root_str = root.to_string_lossy().to_string();
+    let prefix = format!("{}{}%", escape_like_literal(&root_str), MAIN_SEPARATOR);
+    let stored: Vec<String> = stmt
+        .query_map(params![root_str, prefix], |row| row.get(0))?
+        .filter_map(Result::ok)
+        .collect();
+    Ok(stored
+        .into_iter()
+        .filter(|p| !seen_paths.contains(p))
+        .collect())
+}
+
+fn escape_like_literal(raw: &str) -> String {
+    let esc = LIKE_ESCAPE;
+    raw.replace(esc, &format!("{esc}{esc}"))
+        .replace('%', &format!("{esc}%"))
+        .replace('_', &format!("{esc}_"))
+}
+
+fn walk(dir: &Path, out: &mut Vec<(PathBuf, fs::Metadata)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, out);
+        } else if let Ok(metadata) = entry.metadata() {
+            out.push((path, metadata));
+        }
+    }
+}
+
+fn modified_secs(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -5,62 +5,267 @@
 //     file_organizer_lib::run()
 // }
 
+mod duplicates;
+mod index;
+mod mime;
+mod organize;
+mod scan;
+
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 use tauri::command;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+pub(crate) use duplicates::find_duplicates;
+pub(crate) use index::{init_index, query_index, rescan};
+pub(crate) use organize::{apply_plan, plan_organize, undo_last_organize};
+pub(crate) use scan::{cancel_scan, scan_directory_streaming, CancelFlag};
 
 #[derive(Serialize)]
-struct FileMeta {
-    name: String,
-    path: String,
-    file_type: String,
-    size: u64,
-    created: String,
-    modified: String,
+pub(crate) struct FileMeta {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) file_type: String,
+    /// Broad content category, e.g. `"image"`, `"code"`; `"directory"` for
+    /// directories. Sniffed from magic bytes when content detection is on
+    /// (falling back to an extension-based guess when sniffing is
+    /// inconclusive), otherwise guessed from the extension alone.
+    pub(crate) category: String,
+    pub(crate) size: u64,
+    /// Unix-epoch seconds; `0` when the platform/filesystem can't report it.
+    pub(crate) created: u64,
+    pub(crate) modified: u64,
+    pub(crate) accessed: u64,
+    pub(crate) is_directory: bool,
+    pub(crate) is_symlink: bool,
+    /// POSIX permission string (e.g. `"rwxr-xr-x"`) on Unix; `None` elsewhere.
+    pub(crate) permissions: Option<String>,
+    /// Number of direct entries, only populated for directories.
+    pub(crate) child_count: Option<u64>,
+}
+
+/// Optional filtering applied while walking a tree. `ignore_patterns`, when
+/// omitted, falls back to the nearest `.gitignore` at the scan root.
+///
+/// `detect_content_category` opts into sniffing each file's magic bytes for
+/// `FileMeta::category` (see [`mime::detect_category`]); left at its default
+/// of `false`, `category` is filled in from the extension alone, since
+/// opening and reading every file in a large tree is too costly to pay
+/// unconditionally on every scan.
+#[derive(Deserialize, Default)]
+pub(crate) struct ScanOptions {
+    #[serde(default)]
+    include_hidden: bool,
+    ignore_patterns: Option<Vec<String>>,
+    #[serde(default)]
+    detect_content_category: bool,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ScanResult {
+    files: Vec<FileMeta>,
+    pruned_count: u64,
 }
 
 #[command]
-fn scan_directory(path: String) -> Vec<FileMeta> {
+fn scan_directory(path: String, options: Option<ScanOptions>) -> ScanResult {
+    let options = options.unwrap_or_default();
+    let root = PathBuf::from(&path);
+    let patterns = resolve_ignore_patterns(&root, &options);
+
     let mut files = Vec::new();
-    visit_dirs(PathBuf::from(path), &mut files);
-    files
+    let mut pruned_count = 0;
+    visit_dirs(&root, &root, &options, &patterns, &mut files, &mut pruned_count);
+    ScanResult { files, pruned_count }
 }
 
-fn visit_dirs(dir: PathBuf, files: &mut Vec<FileMeta>) {
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                visit_dirs(path, files);
-            } else if let Ok(metadata) = entry.metadata() {
-                let created = metadata.created().ok()
-                    .and_then(|c| Some(format!("{:?}", c)))
-                    .unwrap_or("unknown".into());
-                let modified = metadata.modified().ok()
-                    .and_then(|m| Some(format!("{:?}", m)))
-                    .unwrap_or("unknown".into());
-                files.push(FileMeta {
-                    name: entry.file_name().to_string_lossy().to_string(),
-                    path: path.to_string_lossy().to_string(),
-                    file_type: path.extension()
-                        .and_then(|ext| ext.to_str())
-                        .unwrap_or("unknown").to_string(),
-                    size: metadata.len(),
-                    created,
-                    modified,
-                });
+/// Compiles `options.ignore_patterns` (or the root's `.gitignore`) into
+/// matchable globs.
+///
+/// This only supports a narrow subset of gitignore syntax: a pattern
+/// containing `/` is anchored to `root` and matched against the full
+/// relative path, a pattern with no `/` is matched against the bare file
+/// name at any depth. A trailing `/` is stripped before compiling, so such a
+/// pattern matches files and directories alike; directory-only matching is
+/// not supported. Relative paths carry no leading `/`, so a pattern written
+/// with one (e.g. `/build`) matches nothing. Negation (`!pattern`) isn't implemented — compiling it
+/// as a literal glob would silently fail to un-ignore anything, which is
+/// worse than not matching it at all, so [`read_gitignore`] drops those
+/// lines instead.
+fn resolve_ignore_patterns(root: &Path, options: &ScanOptions) -> Vec<glob::Pattern> {
+    let raw = options
+        .ignore_patterns
+        .clone()
+        .unwrap_or_else(|| read_gitignore(root));
+    raw.iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern.trim_end_matches('/')).ok())
+        .collect()
+}
+
+/// Reads `root`'s `.gitignore`, dropping comments, blank lines, and negation
+/// (`!pattern`) lines, since negation isn't supported by
+/// [`resolve_ignore_patterns`] — see its doc comment for the supported
+/// subset.
+fn read_gitignore(root: &Path) -> Vec<String> {
+    fs::read_to_string(root.join(".gitignore"))
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_ignored(relative: &Path, patterns: &[glob::Pattern]) -> bool {
+    let relative_str = relative.to_string_lossy();
+    patterns.iter().any(|pattern| {
+        pattern.matches(&relative_str)
+            || relative
+                .file_name()
+                .map(|name| pattern.matches(&name.to_string_lossy()))
+                .unwrap_or(false)
+    })
+}
+
+fn visit_dirs(
+    root: &Path,
+    dir: &Path,
+    options: &ScanOptions,
+    patterns: &[glob::Pattern],
+    files: &mut Vec<FileMeta>,
+    pruned_count: &mut u64,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if !options.include_hidden && name.starts_with('.') {
+            *pruned_count += 1;
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        if is_ignored(relative, patterns) {
+            *pruned_count += 1;
+            continue;
+        }
+
+        if let Some(meta) = file_meta_from_entry_opts(&path, options.detect_content_category) {
+            let is_dir = meta.is_directory;
+            files.push(meta);
+            if is_dir {
+                visit_dirs(root, &path, options, patterns, files, pruned_count);
             }
         }
     }
 }
 
+/// Builds a `FileMeta` for a single path, sniffing its content for
+/// [`FileMeta::category`]. Shared by commands that describe one-off entries
+/// (duplicates, index) where the extra open+read is negligible
+/// next to the work they already do.
+pub(crate) fn file_meta_from_entry(path: &Path) -> Option<FileMeta> {
+    file_meta_from_entry_opts(path, true)
+}
+
+/// Builds a `FileMeta` for a single path, shared by the full-tree scan and
+/// any command that needs to describe one entry without walking a whole
+/// tree. Uses `symlink_metadata` so a symlink is described as itself rather
+/// than silently following it into its target (and possibly a cycle).
+///
+/// `detect_content_category` controls whether `category` is sniffed from
+/// magic bytes ([`mime::detect_category`]) or guessed from the extension
+/// alone ([`mime::category_from_extension`]) — the latter costs no syscalls
+/// beyond the metadata already being read, which matters when this runs once
+/// per entry across a large tree.
+pub(crate) fn file_meta_from_entry_opts(path: &Path, detect_content_category: bool) -> Option<FileMeta> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let is_directory = metadata.is_dir();
+    let child_count = is_directory
+        .then(|| fs::read_dir(path).ok().map(|d| d.flatten().count() as u64))
+        .flatten();
+    let category = if is_directory {
+        "directory".to_string()
+    } else if detect_content_category {
+        mime::detect_category(path)
+    } else {
+        mime::category_from_extension(path)
+    };
+
+    Some(FileMeta {
+        name,
+        path: path.to_string_lossy().to_string(),
+        file_type: path.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("unknown").to_string(),
+        category,
+        size: metadata.len(),
+        created: epoch_secs(metadata.created()),
+        modified: epoch_secs(metadata.modified()),
+        accessed: epoch_secs(metadata.accessed()),
+        is_directory,
+        is_symlink: metadata.is_symlink(),
+        permissions: permissions_string(&metadata),
+        child_count,
+    })
+}
+
+fn epoch_secs(time: std::io::Result<std::time::SystemTime>) -> u64 {
+    time.ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(unix)]
+fn permissions_string(metadata: &fs::Metadata) -> Option<String> {
+    let mode = metadata.permissions().mode();
+    let bit = |shift: u32, ch: char| if mode & (1 << shift) != 0 { ch } else { '-' };
+    Some(format!(
+        "{}{}{}{}{}{}{}{}{}",
+        bit(8, 'r'), bit(7, 'w'), bit(6, 'x'),
+        bit(5, 'r'), bit(4, 'w'), bit(3, 'x'),
+        bit(2, 'r'), bit(1, 'w'), bit(0, 'x'),
+    ))
+}
+
+#[cfg(not(unix))]
+fn permissions_string(_metadata: &fs::Metadata) -> Option<String> {
+    None
+}
+
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![scan_directory])
+        .manage(CancelFlag::default())
+        .invoke_handler(tauri::generate_handler![
+            scan_directory,
+            find_duplicates,
+            init_index,
+            rescan,
+            query_index,
+            plan_organize,
+            apply_plan,
+            undo_last_organize,
+            scan_directory_streaming,
+            cancel_scan
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-        
-      
+
+
 }
@@ -0,0 +1,99 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a file when sniffing its magic number.
+const SNIFF_BYTES: usize = 16;
+
+/// Infers a broad content category (`"image"`, `"video"`, `"audio"`,
+/// `"archive"`, `"document"`, `"code"`, `"unknown"`) for `path`.
+///
+/// Leading magic bytes are checked first since they describe the file's
+/// actual content; the extension is only consulted when sniffing is
+/// inconclusive (unreadable file, or a signature we don't recognize). This
+/// opens and reads every file it's given, so callers walking large trees
+/// (e.g. `scan_directory`) should prefer [`category_from_extension`] unless a
+/// caller has actually asked for true content detection.
+pub(crate) fn detect_category(path: &Path) -> String {
+    sniff_magic(path)
+        .unwrap_or_else(|| extension_category(path))
+        .to_string()
+}
+
+fn sniff_magic(path: &Path) -> Option<&'static str> {
+    let mut file = File::open(path).ok()?;
+    let mut buf = [0u8; SNIFF_BYTES];
+    let n = file.read(&mut buf).ok()?;
+    let b = &buf[..n];
+
+    if b.starts_with(&[0x89, b'P', b'N', b'G']) {
+        return Some("image");
+    }
+    if b.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return Some("image");
+    }
+    if b.starts_with(b"GIF87a") || b.starts_with(b"GIF89a") {
+        return Some("image");
+    }
+    if b.starts_with(b"BM") {
+        return Some("image");
+    }
+    if b.starts_with(b"%PDF") {
+        return Some("document");
+    }
+    if b.starts_with(&[0x1F, 0x8B]) {
+        return Some("archive");
+    }
+    if b.starts_with(b"Rar!") || b.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        return Some("archive");
+    }
+    if b.starts_with(&[0x50, 0x4B, 0x03, 0x04]) {
+        return Some("archive");
+    }
+    if b.starts_with(b"ID3") || (b.len() >= 2 && b[0] == 0xFF && b[1] & 0xE0 == 0xE0) {
+        return Some("audio");
+    }
+    if b.starts_with(b"OggS") || b.starts_with(b"fLaC") {
+        return Some("audio");
+    }
+    if b.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        return Some("video");
+    }
+    if b.len() >= 12 && &b[4..8] == b"ftyp" {
+        return Some("video");
+    }
+    if b.starts_with(b"RIFF") && b.len() >= 12 {
+        if &b[8..12] == b"WAVE" {
+            return Some("audio");
+        }
+        if &b[8..12] == b"AVI " {
+            return Some("video");
+        }
+    }
+
+    None
+}
+
+/// Extension-only category guess: no file is opened. Used where sniffing
+/// every entry's magic bytes would be too costly, e.g. a full-tree scan.
+pub(crate) fn category_from_extension(path: &Path) -> String {
+    extension_category(path).to_string()
+}
+
+fn extension_category(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match ext.as_str() {
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "tiff" => "image",
+        "mp4" | "mov" | "avi" | "mkv" | "webm" => "video",
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" | "aac" => "audio",
+        "zip" | "tar" | "gz" | "rar" | "7z" | "bz2" => "archive",
+        "pdf" | "doc" | "docx" | "txt" | "md" | "odt" | "rtf" => "document",
+        "rs" | "py" | "js" | "ts" | "go" | "c" | "cpp" | "h" | "java" | "rb" | "sh" => "code",
+        _ => "unknown",
+    }
+}
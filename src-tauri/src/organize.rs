@@ -0,0 +1,265 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+
+const JOURNAL_FILE_NAME: &str = "last_organize_journal.json";
+
+/// A single matcher/destination pair. The first rule whose matcher accepts a
+/// file wins; files matching no rule are left where they are.
+#[derive(Deserialize, Clone)]
+pub(crate) struct OrganizeRule {
+    matcher: RuleMatcher,
+    /// Subfolder created under the scanned directory, e.g. `"Images"`.
+    destination: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum RuleMatcher {
+    Extension { extensions: Vec<String> },
+    SizeRange { min: Option<u64>, max: Option<u64> },
+    ModifiedBucket { bucket: ModifiedBucket },
+    /// Matches on the sniffed content category (see [`crate::mime`]) rather
+    /// than a possibly-wrong extension, e.g. `"image"` or `"archive"`.
+    Category { categories: Vec<String> },
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ModifiedBucket {
+    Today,
+    ThisWeek,
+    ThisMonth,
+    Older,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct PlannedMove {
+    from: String,
+    to: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub(crate) struct OrganizePlan {
+    moves: Vec<PlannedMove>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct MoveResult {
+    from: String,
+    to: String,
+    success: bool,
+    error: Option<String>,
+}
+
+/// Matches every direct child file of `path` against `rules` in order and
+/// proposes a destination for each one, without touching the filesystem.
+/// Destinations that would collide with an existing file or an earlier move
+/// in the same plan get an auto-incrementing `" (n)"` suffix.
+#[command]
+pub(crate) fn plan_organize(path: String, rules: Vec<OrganizeRule>) -> OrganizePlan {
+    let root = PathBuf::from(&path);
+    let mut taken: Vec<PathBuf> = Vec::new();
+    let mut moves = Vec::new();
+
+    let Ok(entries) = fs::read_dir(&root) else {
+        return OrganizePlan::default();
+    };
+
+    for entry in entries.flatten() {
+        let file_path = entry.path();
+        if file_path.is_dir() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let Some(rule) = rules.iter().find(|r| matches(r, &file_path, &metadata)) else {
+            continue;
+        };
+
+        let dest_dir = root.join(&rule.destination);
+        let file_name = entry.file_name();
+        let candidate = unique_destination(&dest_dir, Path::new(&file_name), &taken);
+        taken.push(candidate.clone());
+        moves.push(PlannedMove {
+            from: file_path.to_string_lossy().to_string(),
+            to: candidate.to_string_lossy().to_string(),
+        });
+    }
+
+    OrganizePlan { moves }
+}
+
+/// Executes a plan produced by [`plan_organize`]. Each move is attempted with
+/// `fs::rename` first; if that fails (e.g. the destination is on a different
+/// filesystem), it falls back to copying the file then deleting the
+/// original. The moves actually performed are written to a journal, replacing
+/// any earlier one, so [`undo_last_organize`] can restore the tree.
+#[command]
+pub(crate) fn apply_plan(app: AppHandle, plan: OrganizePlan) -> Vec<MoveResult> {
+    let mut performed = Vec::new();
+    let mut results = Vec::new();
+
+    for planned in &plan.moves {
+        let from = PathBuf::from(&planned.from);
+        let to = PathBuf::from(&planned.to);
+        match move_file(&from, &to) {
+            Ok(()) => {
+                performed.push(planned.clone());
+                results.push(MoveResult {
+                    from: planned.from.clone(),
+                    to: planned.to.clone(),
+                    success: true,
+                    error: None,
+                });
+            }
+            Err(e) => results.push(MoveResult {
+                from: planned.from.clone(),
+                to: planned.to.clone(),
+                success: false,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    // Always overwrite the journal, even with an empty move list, so a run
+    // that moved nothing can't leave a stale journal from an earlier run for
+    // `undo_last_organize` to replay.
+    let _ = write_journal(&app, &OrganizePlan { moves: performed });
+    results
+}
+
+/// Reverses the moves recorded by the most recent [`apply_plan`] call,
+/// restoring every successfully-moved file to its original path.
+#[command]
+pub(crate) fn undo_last_organize(app: AppHandle) -> Result<Vec<MoveResult>, String> {
+    let journal = read_journal(&app).map_err(|e| e.to_string())?;
+    let mut results = Vec::new();
+
+    for planned in journal.moves.iter().rev() {
+        let from = PathBuf::from(&planned.to);
+        let to = PathBuf::from(&planned.from);
+        match move_file(&from, &to) {
+            Ok(()) => results.push(MoveResult {
+                from: planned.to.clone(),
+                to: planned.from.clone(),
+                success: true,
+                error: None,
+            }),
+            Err(e) => results.push(MoveResult {
+                from: planned.to.clone(),
+                to: planned.from.clone(),
+                success: false,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    let _ = clear_journal(&app);
+    Ok(results)
+}
+
+fn matches(rule: &OrganizeRule, path: &Path, metadata: &fs::Metadata) -> bool {
+    match &rule.matcher {
+        RuleMatcher::Extension { extensions } => path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false),
+        RuleMatcher::SizeRange { min, max } => {
+            let size = metadata.len();
+            min.map(|m| size >= m).unwrap_or(true) && max.map(|m| size <= m).unwrap_or(true)
+        }
+        RuleMatcher::ModifiedBucket { bucket } => modified_bucket(metadata) == *bucket,
+        RuleMatcher::Category { categories } => {
+            let category = crate::mime::detect_category(path);
+            categories.iter().any(|c| c.eq_ignore_ascii_case(&category))
+        }
+    }
+}
+
+fn modified_bucket(metadata: &fs::Metadata) -> ModifiedBucket {
+    let Ok(modified) = metadata.modified() else {
+        return ModifiedBucket::Older;
+    };
+    let Ok(age) = SystemTime::now().duration_since(modified) else {
+        return ModifiedBucket::Today;
+    };
+    let days = age.as_secs() / 86_400;
+    if days < 1 {
+        ModifiedBucket::Today
+    } else if days < 7 {
+        ModifiedBucket::ThisWeek
+    } else if days < 30 {
+        ModifiedBucket::ThisMonth
+    } else {
+        ModifiedBucket::Older
+    }
+}
+
+fn unique_destination(dest_dir: &Path, file_name: &Path, taken: &[PathBuf]) -> PathBuf {
+    let stem = file_name
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = file_name.extension().map(|e| e.to_string_lossy().to_string());
+
+    let mut candidate = dest_dir.join(file_name);
+    let mut n = 1;
+    while candidate.exists() || taken.contains(&candidate) {
+        let suffixed = match &ext {
+            Some(ext) => format!("{stem} ({n}).{ext}"),
+            None => format!("{stem} ({n})"),
+        };
+        candidate = dest_dir.join(suffixed);
+        n += 1;
+    }
+    candidate
+}
+
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)?;
+    Ok(())
+}
+
+fn journal_path(app: &AppHandle) -> PathBuf {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let _ = fs::create_dir_all(&dir);
+    dir.join(JOURNAL_FILE_NAME)
+}
+
+fn write_journal(app: &AppHandle, plan: &OrganizePlan) -> std::io::Result<()> {
+    let json = serde_json::to_string(plan)?;
+    fs::write(journal_path(app), json)
+}
+
+fn read_journal(app: &AppHandle) -> std::io::Result<OrganizePlan> {
+    let path = journal_path(app);
+    if !path.exists() {
+        return Ok(OrganizePlan::default());
+    }
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| e.into())
+}
+
+fn clear_journal(app: &AppHandle) -> std::io::Result<()> {
+    let path = journal_path(app);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}